@@ -0,0 +1,61 @@
+use clap::ValueEnum;
+
+/// Output format for the extracted word list.
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// `word,frequency,document_frequency` rows
+    Csv,
+    /// A numbered diceware-style wordlist, one `<dice-roll>,<word>` pair per line
+    Diceware,
+}
+
+/// Smallest number of base-6 digits `n >= 1` such that `6^n >= count`, matching the classic
+/// diceware convention of rolling one die per digit. A `count` of 0 or 1 still yields 1 digit.
+pub fn dice_digits(count: usize) -> usize {
+    let mut digits = 1;
+    while 6usize.pow(digits as u32) < count {
+        digits += 1;
+    }
+    digits
+}
+
+/// Render the 0-based `index` as a `digits`-long base-6 roll using the digits 1-6, e.g. index 0
+/// with 5 digits is `"11111"` and index 1 is `"11112"`.
+pub fn dice_index(index: usize, digits: usize) -> String {
+    let mut value = index;
+    let mut rolls = vec![0u8; digits];
+
+    for roll in rolls.iter_mut().rev() {
+        *roll = (value % 6) as u8 + 1;
+        value /= 6;
+    }
+
+    rolls.iter().map(|d| d.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digits_cover_the_count() {
+        assert_eq!(dice_digits(0), 1);
+        assert_eq!(dice_digits(1), 1);
+        assert_eq!(dice_digits(6), 1);
+        assert_eq!(dice_digits(7), 2);
+        assert_eq!(dice_digits(36), 2);
+        assert_eq!(dice_digits(37), 3);
+    }
+
+    #[test]
+    fn index_zero_is_all_ones() {
+        assert_eq!(dice_index(0, 5), "11111");
+    }
+
+    #[test]
+    fn index_increments_like_a_base_six_odometer() {
+        assert_eq!(dice_index(1, 5), "11112");
+        assert_eq!(dice_index(6, 5), "11121");
+        assert_eq!(dice_index(35, 2), "66");
+    }
+}
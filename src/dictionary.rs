@@ -0,0 +1,182 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A loaded dictionary used to filter out misspellings, OCR artifacts, and other garbage tokens
+/// that shouldn't make it into a typing-game word list.
+pub struct Dictionary {
+    words: HashSet<String>,
+    by_length: HashMap<usize, Vec<String>>,
+    max_edit: u8,
+}
+
+impl Dictionary {
+    /// Load one word per line from `path`, lowercased to match the tokenizer's output.
+    pub fn load(path: &Path, max_edit: u8) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+
+        let words: HashSet<String> = contents
+            .lines()
+            .map(|line| line.trim().to_lowercase())
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        let mut by_length: HashMap<usize, Vec<String>> = HashMap::new();
+        for word in &words {
+            by_length
+                .entry(word.chars().count())
+                .or_default()
+                .push(word.clone());
+        }
+
+        //sorted so that distance ties break the same way on every run, regardless of the
+        //HashSet's randomized iteration order
+        for bucket in by_length.values_mut() {
+            bucket.sort_unstable();
+        }
+
+        Ok(Dictionary {
+            words,
+            by_length,
+            max_edit,
+        })
+    }
+
+    /// Return the canonical dictionary spelling for `word`: itself if it's an exact match, or
+    /// the closest dictionary word within `max_edit` Damerau-Levenshtein distance. Only
+    /// candidates whose length differs by at most one are compared. Ties on distance are broken
+    /// alphabetically so the result is stable across runs.
+    pub fn correct(&self, word: &str) -> Option<String> {
+        if self.words.contains(word) {
+            return Some(word.to_string());
+        }
+
+        if self.max_edit == 0 {
+            return None;
+        }
+
+        let word_len = word.chars().count();
+
+        (word_len.saturating_sub(1)..=word_len + 1)
+            .filter_map(|len| self.by_length.get(&len))
+            .flatten()
+            .filter_map(|candidate| {
+                let distance = damerau_levenshtein(word, candidate, self.max_edit);
+                (distance <= self.max_edit as usize).then_some((distance, candidate))
+            })
+            .min_by_key(|(distance, candidate)| (*distance, candidate.as_str()))
+            .map(|(_, candidate)| candidate.clone())
+    }
+}
+
+/// Damerau-Levenshtein edit distance (insertion, deletion, substitution, and adjacent
+/// transposition all cost 1), e.g. `teh` -> `the` is distance 1. Aborts a row early once its
+/// running minimum exceeds `max`, returning `max + 1` in that case since the caller only cares
+/// whether the real distance is within budget.
+fn damerau_levenshtein(a: &str, b: &str, max: u8) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (m, n) = (a.len(), b.len());
+    let max = max as usize;
+
+    let mut d = vec![vec![0usize; n + 1]; m + 1];
+
+    for (i, row) in d.iter_mut().enumerate().take(m + 1) {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=m {
+        let mut row_min = usize::MAX;
+
+        for j in 1..=n {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+
+            row_min = row_min.min(d[i][j]);
+        }
+
+        if row_min > max {
+            return max + 1;
+        }
+    }
+
+    d[m][n]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dict(words: &[&str], max_edit: u8) -> Dictionary {
+        let words: HashSet<String> = words.iter().map(|w| w.to_string()).collect();
+
+        let mut by_length: HashMap<usize, Vec<String>> = HashMap::new();
+        for word in &words {
+            by_length
+                .entry(word.chars().count())
+                .or_default()
+                .push(word.clone());
+        }
+        for bucket in by_length.values_mut() {
+            bucket.sort_unstable();
+        }
+
+        Dictionary {
+            words,
+            by_length,
+            max_edit,
+        }
+    }
+
+    #[test]
+    fn exact_distance_cases() {
+        assert_eq!(damerau_levenshtein("cat", "cat", 2), 0);
+        assert_eq!(damerau_levenshtein("cat", "cot", 2), 1);
+        assert_eq!(damerau_levenshtein("teh", "the", 2), 1);
+        //real distance is 3; early abort caps it at max + 1
+        assert_eq!(damerau_levenshtein("kitten", "sitting", 1), 2);
+    }
+
+    #[test]
+    fn exact_match_is_returned_as_is() {
+        let d = dict(&["the", "cat"], 1);
+        assert_eq!(d.correct("cat"), Some("cat".to_string()));
+    }
+
+    #[test]
+    fn adjacent_transposition_counts_as_distance_one() {
+        let d = dict(&["the"], 1);
+        assert_eq!(d.correct("teh"), Some("the".to_string()));
+    }
+
+    #[test]
+    fn distance_above_threshold_is_rejected() {
+        let d = dict(&["the"], 1);
+        assert_eq!(d.correct("tehx"), None);
+    }
+
+    #[test]
+    fn max_edit_zero_disables_fuzzy_matching() {
+        let d = dict(&["the"], 0);
+        assert_eq!(d.correct("teh"), None);
+    }
+
+    #[test]
+    fn ties_break_the_same_way_on_every_call() {
+        let d = dict(&["cat", "cog"], 1);
+        for _ in 0..20 {
+            assert_eq!(d.correct("cot"), Some("cat".to_string()));
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use clap::ValueEnum;
+
+/// How to handle a file whose bytes can't be decoded as valid UTF-8.
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum OnInvalid {
+    /// Skip the file and keep going
+    Skip,
+    /// Decode it anyway, replacing invalid sequences with U+FFFD
+    Lossy,
+    /// Stop the whole run
+    Abort,
+}
+
+/// Outcome of probing a file's raw bytes before tokenizing it.
+pub enum Decoded {
+    /// File is 3 bytes long or shorter and is not tokenized
+    Empty,
+    /// File looks like binary data rather than text. Carries the BOM-stripped bytes, so a
+    /// caller that decides to decode it anyway (lossily) doesn't leak the BOM into the text.
+    Binary(Vec<u8>),
+    /// Decoded text content
+    Text(String),
+}
+
+const PROBE_LEN: usize = 64;
+const CONTROL_BYTE_RATIO_THRESHOLD: f32 = 0.3;
+
+/// Inspect a file's raw bytes and either classify it as empty/binary or decode it to a `String`,
+/// following the user's `on_invalid` policy for bytes that turn out not to be valid UTF-8.
+pub fn decode(buf: &[u8], on_invalid: OnInvalid) -> Decoded {
+    if buf.len() <= 3 {
+        return Decoded::Empty;
+    }
+
+    if let Some(text) = decode_utf16_bom(buf) {
+        return Decoded::Text(text);
+    }
+
+    let body = strip_utf8_bom(buf);
+
+    if looks_binary(body) {
+        return Decoded::Binary(body.to_vec());
+    }
+
+    match std::str::from_utf8(body) {
+        Ok(text) => Decoded::Text(text.to_string()),
+        Err(_) => match on_invalid {
+            OnInvalid::Skip => Decoded::Binary(body.to_vec()),
+            OnInvalid::Lossy => Decoded::Text(String::from_utf8_lossy(body).to_string()),
+            OnInvalid::Abort => {
+                eprintln!("Encountered invalid UTF-8 and --on-invalid=abort is set, aborting.");
+                std::process::abort();
+            }
+        },
+    }
+}
+
+fn strip_utf8_bom(buf: &[u8]) -> &[u8] {
+    buf.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(buf)
+}
+
+fn decode_utf16_bom(buf: &[u8]) -> Option<String> {
+    if buf.len() >= 2 && buf[0] == 0xFF && buf[1] == 0xFE {
+        return Some(decode_utf16(&buf[2..], u16::from_le_bytes));
+    }
+    if buf.len() >= 2 && buf[0] == 0xFE && buf[1] == 0xFF {
+        return Some(decode_utf16(&buf[2..], u16::from_be_bytes));
+    }
+    None
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| from_bytes([pair[0], pair[1]]))
+        .collect();
+
+    String::from_utf16_lossy(&units)
+}
+
+/// Sniff the first `PROBE_LEN` bytes for NUL bytes or a high ratio of non-text control bytes,
+/// the same heuristic `file(1)` and most text editors use to flag binary content.
+fn looks_binary(buf: &[u8]) -> bool {
+    let probe = &buf[..buf.len().min(PROBE_LEN)];
+
+    if probe.contains(&0) {
+        return true;
+    }
+
+    let control_bytes = probe
+        .iter()
+        .filter(|&&b| b < 0x20 && b != b'\t' && b != b'\n' && b != b'\r')
+        .count();
+
+    (control_bytes as f32 / probe.len() as f32) > CONTROL_BYTE_RATIO_THRESHOLD
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(decoded: Decoded) -> String {
+        match decoded {
+            Decoded::Text(text) => text,
+            Decoded::Empty => panic!("expected Text, got Empty"),
+            Decoded::Binary(_) => panic!("expected Text, got Binary"),
+        }
+    }
+
+    #[test]
+    fn tiny_files_are_empty() {
+        assert!(matches!(decode(b"hi", OnInvalid::Lossy), Decoded::Empty));
+        assert!(matches!(decode(b"", OnInvalid::Lossy), Decoded::Empty));
+    }
+
+    #[test]
+    fn plain_ascii_decodes_as_text() {
+        assert_eq!(text(decode(b"hello world", OnInvalid::Lossy)), "hello world");
+    }
+
+    #[test]
+    fn utf8_bom_is_stripped() {
+        let mut buf = vec![0xEF, 0xBB, 0xBF];
+        buf.extend_from_slice("hello".as_bytes());
+        assert_eq!(text(decode(&buf, OnInvalid::Lossy)), "hello");
+    }
+
+    #[test]
+    fn utf16_le_bom_is_decoded() {
+        let mut buf = vec![0xFF, 0xFE];
+        for unit in "hi".encode_utf16() {
+            buf.extend_from_slice(&unit.to_le_bytes());
+        }
+        assert_eq!(text(decode(&buf, OnInvalid::Lossy)), "hi");
+    }
+
+    #[test]
+    fn utf16_be_bom_is_decoded() {
+        let mut buf = vec![0xFE, 0xFF];
+        for unit in "hi".encode_utf16() {
+            buf.extend_from_slice(&unit.to_be_bytes());
+        }
+        assert_eq!(text(decode(&buf, OnInvalid::Lossy)), "hi");
+    }
+
+    #[test]
+    fn nul_bytes_are_classified_as_binary() {
+        let buf = b"some\0binary\0junk\0here\0padding".to_vec();
+        assert!(matches!(decode(&buf, OnInvalid::Lossy), Decoded::Binary(_)));
+    }
+
+    #[test]
+    fn high_control_byte_ratio_is_classified_as_binary() {
+        let buf: Vec<u8> = (0..64).map(|i| if i % 2 == 0 { 0x01 } else { b'a' }).collect();
+        assert!(matches!(decode(&buf, OnInvalid::Lossy), Decoded::Binary(_)));
+    }
+
+    #[test]
+    fn invalid_utf8_skip_is_reported_as_binary() {
+        let buf = vec![b'a'; 40]
+            .into_iter()
+            .chain([0xFF, 0xFE, 0xFD])
+            .collect::<Vec<u8>>();
+        assert!(matches!(decode(&buf, OnInvalid::Skip), Decoded::Binary(_)));
+    }
+
+    #[test]
+    fn binary_classification_strips_the_utf8_bom_from_the_carried_bytes() {
+        let mut buf = vec![0xEF, 0xBB, 0xBF];
+        buf.extend(std::iter::repeat(0x01).take(40));
+        match decode(&buf, OnInvalid::Lossy) {
+            Decoded::Binary(bytes) => assert!(!bytes.starts_with(&[0xEF, 0xBB, 0xBF])),
+            _ => panic!("expected Binary, got a different variant"),
+        }
+    }
+
+    #[test]
+    fn invalid_utf8_lossy_replaces_bad_sequences() {
+        let buf = vec![b'a'; 40]
+            .into_iter()
+            .chain([0xFF])
+            .collect::<Vec<u8>>();
+        assert!(text(decode(&buf, OnInvalid::Lossy)).contains('\u{fffd}'));
+    }
+}
@@ -0,0 +1,119 @@
+use clap::ValueEnum;
+
+/// Which language's tokenization rules to apply.
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Language {
+    /// English (ASCII only, preserves the original behavior)
+    En,
+    De,
+    Fr,
+    Es,
+}
+
+/// Per-language rules for tokenizing and filtering words: which characters make up a word,
+/// which are trimmed off its edges, which vowels count for the "missing vowels" filter, and
+/// whether the Roman-numeral filter applies at all.
+#[derive(Copy, Clone)]
+pub struct LanguageProfile {
+    pub trim_chars: &'static [char],
+    pub is_word_char: fn(char) -> bool,
+    pub vowels: &'static [char],
+    pub roman_numeral_chars: Option<&'static [char]>,
+}
+
+const BASE_TRIM_CHARS: &[char] = &[
+    '\'', '"', '-', '&', '.', ',', ';', ':', ')', '(', ']', '[', '}', '{',
+];
+
+const FRENCH_TRIM_CHARS: &[char] = &[
+    '\'', '"', '-', '&', '.', ',', ';', ':', ')', '(', ']', '[', '}', '{', '\u{ab}', '\u{bb}',
+];
+
+const ROMAN_NUMERAL_CHARS: &[char] = &['i', 'v', 'x', 'l', 'c', 'd', 'm'];
+
+const EN_VOWELS: &[char] = &['a', 'e', 'i', 'o', 'u'];
+const DE_VOWELS: &[char] = &['a', 'e', 'i', 'o', 'u', '\u{e4}', '\u{f6}', '\u{fc}'];
+const FR_VOWELS: &[char] = &[
+    'a', 'e', 'i', 'o', 'u', 'y', '\u{e0}', '\u{e2}', '\u{e9}', '\u{e8}', '\u{ea}', '\u{eb}',
+    '\u{ee}', '\u{ef}', '\u{f4}', '\u{f9}', '\u{fb}', '\u{fc}', '\u{ff}',
+];
+const ES_VOWELS: &[char] = &[
+    'a', 'e', 'i', 'o', 'u', '\u{e1}', '\u{e9}', '\u{ed}', '\u{f3}', '\u{fa}', '\u{fc}',
+];
+
+impl Language {
+    pub fn profile(self) -> LanguageProfile {
+        match self {
+            Language::En => LanguageProfile {
+                trim_chars: BASE_TRIM_CHARS,
+                is_word_char: |c| c.is_ascii_alphabetic(),
+                vowels: EN_VOWELS,
+                roman_numeral_chars: Some(ROMAN_NUMERAL_CHARS),
+            },
+            Language::De => LanguageProfile {
+                trim_chars: BASE_TRIM_CHARS,
+                is_word_char: char::is_alphabetic,
+                vowels: DE_VOWELS,
+                roman_numeral_chars: None,
+            },
+            Language::Fr => LanguageProfile {
+                trim_chars: FRENCH_TRIM_CHARS,
+                is_word_char: char::is_alphabetic,
+                vowels: FR_VOWELS,
+                roman_numeral_chars: None,
+            },
+            Language::Es => LanguageProfile {
+                trim_chars: BASE_TRIM_CHARS,
+                is_word_char: char::is_alphabetic,
+                vowels: ES_VOWELS,
+                roman_numeral_chars: None,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn en_is_ascii_only() {
+        let profile = Language::En.profile();
+        assert!((profile.is_word_char)('a'));
+        assert!(!(profile.is_word_char)('\u{fc}'));
+    }
+
+    #[test]
+    fn de_allows_umlauts() {
+        let profile = Language::De.profile();
+        assert!((profile.is_word_char)('\u{fc}'));
+        assert!(profile.vowels.contains(&'\u{e4}'));
+        assert!(profile.vowels.contains(&'\u{f6}'));
+        assert!(profile.vowels.contains(&'\u{fc}'));
+    }
+
+    #[test]
+    fn fr_allows_accented_vowels_and_guillemets() {
+        let profile = Language::Fr.profile();
+        assert!((profile.is_word_char)('\u{e9}'));
+        assert!(profile.vowels.contains(&'\u{e9}'));
+        assert!(profile.trim_chars.contains(&'\u{ab}'));
+        assert!(profile.trim_chars.contains(&'\u{bb}'));
+    }
+
+    #[test]
+    fn es_allows_accented_vowels() {
+        let profile = Language::Es.profile();
+        assert!((profile.is_word_char)('\u{f1}'));
+        assert!(profile.vowels.contains(&'\u{e1}'));
+        assert!(profile.vowels.contains(&'\u{fa}'));
+    }
+
+    #[test]
+    fn only_english_enables_the_roman_numeral_filter() {
+        assert!(Language::En.profile().roman_numeral_chars.is_some());
+        assert!(Language::De.profile().roman_numeral_chars.is_none());
+        assert!(Language::Fr.profile().roman_numeral_chars.is_none());
+        assert!(Language::Es.profile().roman_numeral_chars.is_none());
+    }
+}
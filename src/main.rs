@@ -1,13 +1,23 @@
+mod diceware;
+mod dictionary;
 mod directory_walker;
+mod encoding;
+mod language;
 
 use clap::{Parser, ValueEnum};
 use console::style;
 use csv::Writer;
 use dashmap::DashMap;
+use diceware::OutputFormat;
+use dictionary::Dictionary;
 use directory_walker::walker;
+use encoding::{Decoded, OnInvalid};
 use humantime::format_duration;
 use indicatif::{ProgressBar, ProgressStyle};
+use language::Language;
+use rand::seq::SliceRandom;
 use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 use std::fs::File;
 use std::io::{BufReader, Read};
@@ -16,7 +26,7 @@ use std::path::PathBuf;
 use std::time::Instant;
 
 /// A simple program for extracting word frequencies from large quantities of text file.
-/// Developed for the SpeedType game project. Supports English only.
+/// Developed for the SpeedType game project.
 #[derive(Parser)]
 #[clap(author, version, about, long_about = None)]
 struct Cli {
@@ -31,6 +41,61 @@ struct Cli {
     /// CSV output file
     #[clap(short, long, value_parser = clap::value_parser!(PathBuf), value_name = "FILE")]
     output: PathBuf,
+
+    /// Only keep the N most frequent words in CSV format
+    #[clap(long, value_name = "N")]
+    top: Option<usize>,
+
+    /// Skip files that look like binary data instead of tokenizing them
+    #[clap(long, default_value_t = true, action = clap::ArgAction::Set)]
+    skip_binary: bool,
+
+    /// How to handle a file whose bytes can't be decoded as valid text
+    #[clap(long, value_enum, default_value_t = OnInvalid::Lossy)]
+    on_invalid: OnInvalid,
+
+    /// Language profile controlling which characters count as letters, vowels, and filters
+    #[clap(long, value_enum, default_value_t = Language::En)]
+    language: Language,
+
+    /// Dictionary file (one word per line) used to drop misspellings and OCR garbage
+    #[clap(long, value_parser = clap::value_parser!(PathBuf), value_name = "FILE")]
+    dictionary: Option<PathBuf>,
+
+    /// Max Damerau-Levenshtein distance allowed when matching a word against the dictionary
+    #[clap(long, default_value_t = 0, value_parser = clap::value_parser!(u8).range(0..=1))]
+    max_edit: u8,
+
+    /// Cap the number of rayon worker threads (defaults to the number of logical CPUs)
+    #[clap(long, value_name = "N")]
+    threads: Option<usize>,
+
+    /// Output format: a frequency CSV, or a numbered diceware-style wordlist
+    #[clap(long, value_enum, default_value_t = OutputFormat::Csv)]
+    format: OutputFormat,
+
+    /// Number of words to export in diceware format
+    #[clap(long, value_name = "K")]
+    words: Option<usize>,
+
+    /// Drop words shorter than this when exporting diceware format
+    #[clap(long, value_name = "LEN")]
+    min_len: Option<usize>,
+
+    /// Drop words longer than this when exporting diceware format
+    #[clap(long, value_name = "LEN")]
+    max_len: Option<usize>,
+
+    /// Shuffle the diceware wordlist instead of keeping it rank-ordered by frequency
+    #[clap(long)]
+    shuffle: bool,
+}
+
+/// Per-word statistics: total occurrences and number of distinct files the word appears in.
+#[derive(Default, Clone)]
+struct WordStats {
+    freq: u64,
+    docs: u64,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -95,7 +160,28 @@ fn main() {
 
     println!("Got {} files!", style(txt_count).yellow().bold());
 
-    let words = DashMap::new();
+    if let Some(threads) = cli.threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .expect("Failed to configure rayon thread pool");
+    }
+
+    let words: DashMap<String, WordStats> = DashMap::new();
+
+    let profile = cli.language.profile();
+
+    let dictionary = cli.dictionary.as_ref().map(|path| {
+        Dictionary::load(path, cli.max_edit).unwrap_or_else(|_| {
+            println!(
+                "{}",
+                style(format!("Failed to load dictionary {:?}", path))
+                    .red()
+                    .bold(),
+            );
+            std::process::abort();
+        })
+    });
 
     let pb = ProgressBar::new(txt_count);
 
@@ -110,7 +196,7 @@ fn main() {
 
     let start = Instant::now();
 
-    for txt_file in txt_files {
+    txt_files.par_iter().for_each(|txt_file| {
         let file = File::open(txt_file.path()).unwrap_or_else(|_| {
             println!(
                 "Failed to open file {:?}",
@@ -131,60 +217,107 @@ fn main() {
             std::process::abort();
         });
 
-        //read_to_string fails if it encounters non-utf-8 bytes
-        let content = String::from_utf8_lossy(&buf).to_string();
+        let content = match encoding::decode(&buf, cli.on_invalid) {
+            Decoded::Empty => {
+                pb.dec_length(1);
+                return;
+            }
+            Decoded::Binary(bytes) => {
+                if cli.skip_binary || cli.on_invalid == OnInvalid::Skip {
+                    println!(
+                        "{}",
+                        style(format!("Skipping binary file {:?}", txt_file.path()))
+                            .yellow()
+                            .bold(),
+                    );
+                    pb.dec_length(1);
+                    return;
+                }
 
-        let split_lines = content.split("\n");
+                match cli.on_invalid {
+                    OnInvalid::Lossy => String::from_utf8_lossy(&bytes).to_string(),
+                    OnInvalid::Abort => {
+                        println!(
+                            "{}",
+                            style(format!(
+                                "Aborting: {:?} looks like binary data and --on-invalid=abort is set",
+                                txt_file.path()
+                            ))
+                            .red()
+                            .bold(),
+                        );
+                        std::process::abort();
+                    }
+                    OnInvalid::Skip => unreachable!("handled above"),
+                }
+            }
+            Decoded::Text(text) => text,
+        };
 
-        split_lines.par_bridge().for_each(|line| {
-            let split_words = line.split(" ");
+        //accumulated locally and merged into the shared map once per file, rather than on every
+        //word, to keep DashMap contention low
+        let mut local_freq: HashMap<String, u64> = HashMap::new();
+        let mut local_docs: HashSet<String> = HashSet::new();
 
-            for word in split_words {
+        for line in content.split('\n') {
+            for word in line.split(' ') {
                 //remove various special symbols from beginning or end of a word
-                let word = word.trim_matches(&[
-                    '\'', '"', '-', '&', '.', ',', ';', ':', ')', '(', ']', '[', '}', '{',
-                ] as &[_]);
+                let word = word.trim_matches(profile.trim_chars);
 
                 //remove single chars
-                if word.len() < 2 {
+                if word.chars().count() < 2 {
                     continue;
                 }
 
                 //remove words still containing special characters
-                if !word.chars().all(|c| char::is_ascii_alphabetic(&c)) {
+                if !word.chars().all(profile.is_word_char) {
                     continue;
                 }
 
                 //remove words which have an uppercase letter after the beginning
-                if word[1..].chars().any(|c| char::is_ascii_uppercase(&c)) {
+                if word.chars().skip(1).any(char::is_uppercase) {
                     continue;
                 }
 
-                let word = word.to_ascii_lowercase();
+                let word = word.to_lowercase();
 
                 //remove words missing vowels
-                if word
-                    .chars()
-                    .all(|c| !['a', 'e', 'i', 'o', 'u'].contains(&c))
-                {
+                if word.chars().all(|c| !profile.vowels.contains(&c)) {
                     continue;
                 }
 
                 //remove roman numerals
-                if word
-                    .chars()
-                    .all(|c| ['i', 'v', 'x', 'l', 'c', 'd', 'm'].contains(&c))
-                {
-                    continue;
+                if let Some(roman_numeral_chars) = profile.roman_numeral_chars {
+                    if word.chars().all(|c| roman_numeral_chars.contains(&c)) {
+                        continue;
+                    }
                 }
 
-                //add to map or increment counter
-                *words.entry(word.to_string()).or_insert(0) += 1
+                //correct to the nearest dictionary spelling, dropping words with none close enough
+                let word = match &dictionary {
+                    Some(dictionary) => match dictionary.correct(&word) {
+                        Some(canonical) => canonical,
+                        None => continue,
+                    },
+                    None => word,
+                };
+
+                *local_freq.entry(word.clone()).or_insert(0) += 1;
+                local_docs.insert(word);
             }
-        });
+        }
+
+        for (word, freq) in local_freq {
+            words.entry(word).or_default().freq += freq;
+        }
+
+        //a word that occurred anywhere in this file counts once towards its document frequency
+        for word in local_docs {
+            words.entry(word).or_default().docs += 1;
+        }
 
         pb.inc(1);
-    }
+    });
     pb.finish_and_clear();
 
     let duration = format_duration(start.elapsed()).to_string();
@@ -197,13 +330,46 @@ fn main() {
         style(duration).yellow().bold(),
     );
 
-    println!("Writing output CSV to {}", style(csv_path).yellow().bold());
+    println!("Writing output to {}", style(csv_path).yellow().bold());
     let mut wtr = Writer::from_path(csv_path).expect("Couldn't open file for writing");
 
-    words.clone().iter().for_each(|w| {
-        wtr.write_record(&[w.key(), &w.value().to_string()])
-            .expect("Failed to write value");
-    });
+    let mut entries: Vec<(String, WordStats)> = words.into_iter().collect();
+    entries.sort_unstable_by_key(|(_, stats)| std::cmp::Reverse(stats.freq));
+
+    match cli.format {
+        OutputFormat::Csv => {
+            if let Some(top) = cli.top {
+                entries.truncate(top);
+            }
+
+            entries.iter().for_each(|(word, stats)| {
+                wtr.write_record(&[word, &stats.freq.to_string(), &stats.docs.to_string()])
+                    .expect("Failed to write value");
+            });
+        }
+        OutputFormat::Diceware => {
+            let mut candidates: Vec<String> = entries
+                .into_iter()
+                .map(|(word, _)| word)
+                .filter(|word| cli.min_len.is_none_or(|min| word.chars().count() >= min))
+                .filter(|word| cli.max_len.is_none_or(|max| word.chars().count() <= max))
+                .collect();
+
+            let count = cli.words.unwrap_or(candidates.len()).min(candidates.len());
+            candidates.truncate(count);
+
+            if cli.shuffle {
+                candidates.shuffle(&mut rand::thread_rng());
+            }
+
+            let digits = diceware::dice_digits(candidates.len());
+
+            candidates.iter().enumerate().for_each(|(i, word)| {
+                wtr.write_record(&[diceware::dice_index(i, digits), word.clone()])
+                    .expect("Failed to write value");
+            });
+        }
+    }
 
     println!("{}", style("ALL DONE!").green().bold());
 }